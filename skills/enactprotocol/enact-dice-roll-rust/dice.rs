@@ -1,34 +1,623 @@
 use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::process;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Simple random number generator using Linear Congruential Generator
+/// Read a `u64` of entropy from the OS random source.
+///
+/// Returns `None` when the entropy source is unavailable, in which case
+/// callers fall back to time-based seeding.
+fn os_entropy() -> Option<u64> {
+    let mut buf = [0u8; 8];
+    if os_bytes(&mut buf) {
+        Some(u64::from_le_bytes(buf))
+    } else {
+        None
+    }
+}
+
+/// Fill a buffer from the OS random source, returning `false` when entropy
+/// is unavailable.
+///
+/// Without a `Cargo.toml` to pull in the `getrandom` crate this reads
+/// `/dev/urandom` directly, so it is Unix-only: on a platform lacking that
+/// device node the call fails and seed-based callers take their time-based
+/// fallback (the `--secure`/`--commit` modes abort instead).
+fn os_bytes(buf: &mut [u8]) -> bool {
+    File::open("/dev/urandom")
+        .ok()
+        .and_then(|mut f| f.read_exact(buf).ok())
+        .is_some()
+}
+
+/// SHA-256 over `data`, returned as the raw 32-byte digest.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1,
+        0x923f82a4, 0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3,
+        0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786,
+        0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147,
+        0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13,
+        0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a,
+        0x5b9cca4f, 0x682e6ff3, 0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208,
+        0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+    ];
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c,
+        0x1f83d9ab, 0x5be0cd19,
+    ];
+
+    // Pad the message: append 0x80, zeros, then the 64-bit bit length.
+    let mut msg = data.to_vec();
+    let bit_len = (data.len() as u64) * 8;
+    msg.push(0x80);
+    while msg.len() % 64 != 56 {
+        msg.push(0);
+    }
+    msg.extend_from_slice(&bit_len.to_be_bytes());
+
+    for chunk in msg.chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in w.iter_mut().take(16).enumerate() {
+            *word = u32::from_be_bytes([
+                chunk[i * 4],
+                chunk[i * 4 + 1],
+                chunk[i * 4 + 2],
+                chunk[i * 4 + 3],
+            ]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let mut a = h;
+        for i in 0..64 {
+            let s1 = a[4].rotate_right(6) ^ a[4].rotate_right(11) ^ a[4].rotate_right(25);
+            let ch = (a[4] & a[5]) ^ ((!a[4]) & a[6]);
+            let t1 = a[7]
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a[0].rotate_right(2) ^ a[0].rotate_right(13) ^ a[0].rotate_right(22);
+            let maj = (a[0] & a[1]) ^ (a[0] & a[2]) ^ (a[1] & a[2]);
+            let t2 = s0.wrapping_add(maj);
+            a = [
+                t1.wrapping_add(t2),
+                a[0],
+                a[1],
+                a[2],
+                a[3].wrapping_add(t1),
+                a[4],
+                a[5],
+                a[6],
+            ];
+        }
+        for (hi, ai) in h.iter_mut().zip(a.iter()) {
+            *hi = hi.wrapping_add(*ai);
+        }
+    }
+
+    let mut out = [0u8; 32];
+    for (i, word) in h.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+/// Lowercase hex encoding of a byte slice.
+fn to_hex(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        s.push_str(&format!("{:02x}", b));
+    }
+    s
+}
+
+/// Decode a lowercase/uppercase hex string into bytes.
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Roll a uniform value in `[min, max]` directly from OS entropy using
+/// rejection sampling over raw 8-byte draws, so results are unpredictable.
+fn secure_range(min: u64, max: u64) -> u64 {
+    let span = max - min + 1;
+    let zone = span * (u64::MAX / span);
+    loop {
+        let mut buf = [0u8; 8];
+        if !os_bytes(&mut buf) {
+            eprintln!("error: OS entropy unavailable for --secure mode");
+            process::exit(1);
+        }
+        let draw = u64::from_le_bytes(buf);
+        if draw < zone {
+            return min + (draw % span);
+        }
+    }
+}
+
+/// Simple random number generator using a xorshift64 step.
 struct Rng {
     state: u64,
 }
 
 impl Rng {
     fn new() -> Self {
-        // Seed from system time
-        let seed = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos() as u64;
-        Rng { state: seed }
+        // Prefer OS entropy; fall back to nanosecond time if it is unavailable.
+        let seed = os_entropy().unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos() as u64
+        });
+        Rng::from_seed(seed)
+    }
+
+    /// Construct an `Rng` from an explicit seed so a given seed always yields
+    /// the identical sequence of rolls (reproducible for tests and replay).
+    fn from_seed(seed: u64) -> Self {
+        // xorshift64 degenerates to all-zero if seeded with 0; nudge to a
+        // fixed nonzero constant so `from_seed(0)` stays deterministic.
+        let state = if seed == 0 { 0x9e3779b97f4a7c15 } else { seed };
+        Rng { state }
     }
 
     fn next(&mut self) -> u64 {
-        // LCG parameters (same as glibc)
-        self.state = self.state.wrapping_mul(1103515245).wrapping_add(12345);
-        self.state
+        // xorshift64 step: better bit quality than the old glibc LCG.
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
     }
 
     fn range(&mut self, min: u64, max: u64) -> u64 {
-        min + (self.next() % (max - min + 1))
+        // Rejection sampling to avoid modulo bias: draws landing in the final
+        // partial block above `zone` are discarded so every value in the span
+        // is equally likely. The expected number of retries is negligible.
+        let span = max - min + 1;
+        let zone = span * (u64::MAX / span);
+        loop {
+            let draw = self.next();
+            if draw < zone {
+                return min + (draw % span);
+            }
+        }
+    }
+}
+
+/// A parsed dice expression such as `3d6+2`, `d20`, or `2d6+1d4+3`.
+///
+/// `groups` holds one `(count, sides)` pair per dice group and `modifier`
+/// is the signed constant added to the grand total after rolling.
+struct DiceExpr {
+    groups: Vec<(u64, u64)>,
+    modifier: i64,
+}
+
+/// Parse the `NdM(+|-)K` grammar into a [`DiceExpr`].
+///
+/// The expression is split into `+`-separated groups; each group is either a
+/// dice group (`NdM`, with an optional trailing signed modifier like `4d8-1`)
+/// or a bare signed integer modifier. A missing count defaults to `1`, so
+/// `d20` is treated as `1d20`.
+fn parse_expr(expr: &str) -> Result<DiceExpr, String> {
+    let mut groups: Vec<(u64, u64)> = Vec::new();
+    let mut modifier: i64 = 0;
+
+    for token in expr.split('+') {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(format!("empty group in expression '{}'", expr));
+        }
+
+        match token.find('d') {
+            Some(pos) => {
+                // Dice group: `NdM` with an optional trailing signed modifier.
+                let count_str = &token[..pos];
+                let rest = &token[pos + 1..];
+
+                let count: u64 = if count_str.is_empty() {
+                    1
+                } else {
+                    count_str
+                        .parse()
+                        .map_err(|_| format!("invalid dice count '{}'", count_str))?
+                };
+
+                // Scan a trailing signed integer modifier on the sides field.
+                let sides_end = rest.find(|c| c == '-').unwrap_or(rest.len());
+                let sides_str = &rest[..sides_end];
+                let sides: u64 = sides_str
+                    .parse()
+                    .map_err(|_| format!("invalid die size '{}'", sides_str))?;
+
+                if sides < 2 {
+                    return Err(format!("a die needs at least 2 sides, got {}", sides));
+                }
+
+                let tail = &rest[sides_end..];
+                if !tail.is_empty() {
+                    modifier += tail
+                        .parse::<i64>()
+                        .map_err(|_| format!("invalid modifier '{}'", tail))?;
+                }
+
+                groups.push((count, sides));
+            }
+            None => {
+                // Bare modifier group, e.g. the `3` in `2d6+3`.
+                modifier += token
+                    .parse::<i64>()
+                    .map_err(|_| format!("invalid modifier '{}'", token))?;
+            }
+        }
     }
+
+    if groups.is_empty() {
+        return Err(format!("no dice groups in expression '{}'", expr));
+    }
+
+    Ok(DiceExpr { groups, modifier })
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let raw: Vec<String> = env::args().collect();
+
+    // Pull out the optional flags (`--seed`, `--stats`, `--secure`, `--commit`,
+    // `--reveal`, `--commitment`), leaving only positional args.
+    let mut args: Vec<String> = Vec::new();
+    let mut seed: Option<u64> = None;
+    let mut stats: Option<u64> = None;
+    let mut secure = false;
+    let mut commit = false;
+    let mut reveal: Option<String> = None;
+    let mut expect: Option<String> = None;
+    let mut i = 1;
+    while i < raw.len() {
+        if raw[i] == "--seed" {
+            let value = raw.get(i + 1).and_then(|s| s.parse::<u64>().ok());
+            match value {
+                Some(s) => seed = Some(s),
+                None => {
+                    eprintln!("error: --seed requires a u64 value");
+                    process::exit(1);
+                }
+            }
+            i += 2;
+        } else if raw[i] == "--stats" {
+            let value = raw.get(i + 1).and_then(|s| s.parse::<u64>().ok());
+            match value {
+                Some(s) => stats = Some(s),
+                None => {
+                    eprintln!("error: --stats requires a roll count");
+                    process::exit(1);
+                }
+            }
+            i += 2;
+        } else if raw[i] == "--secure" {
+            secure = true;
+            i += 1;
+        } else if raw[i] == "--commit" {
+            commit = true;
+            i += 1;
+        } else if raw[i] == "--reveal" {
+            match raw.get(i + 1) {
+                Some(nonce) => reveal = Some(nonce.clone()),
+                None => {
+                    eprintln!("error: --reveal requires a nonce");
+                    process::exit(1);
+                }
+            }
+            i += 2;
+        } else if raw[i] == "--commitment" {
+            match raw.get(i + 1) {
+                Some(c) => expect = Some(c.clone()),
+                None => {
+                    eprintln!("error: --commitment requires a hex value");
+                    process::exit(1);
+                }
+            }
+            i += 2;
+        } else {
+            args.push(raw[i].clone());
+            i += 1;
+        }
+    }
+    // Re-align to the original 1-based positional indexing used below.
+    args.insert(0, raw[0].clone());
+
+    let mut rng = match seed {
+        Some(s) => Rng::from_seed(s),
+        None => Rng::new(),
+    };
+    // When seeded, surface the seed in the JSON so the run is self-documenting.
+    let mut seed_field = match seed {
+        Some(s) => format!(r#","seed":{}"#, s),
+        None => String::new(),
+    };
+    if secure {
+        seed_field.push_str(r#","secure":true"#);
+    }
+
+    // Commit-reveal workflow for provably-fair rolls. The parameters that
+    // fix the outcome are the positional roll arguments.
+    if commit || reveal.is_some() {
+        let params = args[1..].join(" ");
+        let sides: u64 = args
+            .get(1)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(6)
+            .clamp(2, 100);
+        let count: u64 = args
+            .get(2)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1)
+            .clamp(1, 100);
+
+        // Derive a 32-byte nonce: fresh entropy for --commit, or the supplied
+        // hex for --reveal.
+        let nonce: Vec<u8> = match &reveal {
+            Some(hex) => match from_hex(hex) {
+                Some(n) => n,
+                None => {
+                    eprintln!("error: --reveal nonce must be hex");
+                    process::exit(1);
+                }
+            },
+            None => {
+                let mut n = [0u8; 32];
+                if !os_bytes(&mut n) {
+                    eprintln!("error: OS entropy unavailable for --commit");
+                    process::exit(1);
+                }
+                n.to_vec()
+            }
+        };
+
+        // Commitment binds the nonce to the parameters it will be revealed for.
+        let mut preimage = nonce.clone();
+        preimage.extend_from_slice(params.as_bytes());
+        let commitment = to_hex(&sha256(&preimage));
+
+        if commit {
+            // The publishable object carries only the commitment and the
+            // parameters it binds; the nonce stays secret until the reveal so
+            // the outcome cannot be altered after the fact. Emit the nonce on
+            // stderr, labelled, for the caller to keep and pass to --reveal.
+            eprintln!(
+                "private: keep this nonce secret, pass it to --reveal: {}",
+                to_hex(&nonce)
+            );
+            println!(
+                r#"{{"commitment":"{}","parameters":"{}"}}"#,
+                commitment, params
+            );
+            return;
+        }
+
+        // --reveal: verify the supplied nonce/parameters against the expected
+        // commitment a third party was given earlier, then reproduce the rolls
+        // deterministically from the nonce.
+        let expected = match &expect {
+            Some(c) => c,
+            None => {
+                eprintln!("error: --reveal requires --commitment <hex> to verify against");
+                process::exit(1);
+            }
+        };
+        if !commitment.eq_ignore_ascii_case(expected) {
+            eprintln!(
+                "error: commitment mismatch (expected {}, got {})",
+                expected, commitment
+            );
+            process::exit(1);
+        }
+
+        let digest = sha256(&nonce);
+        let seed = u64::from_le_bytes(digest[..8].try_into().unwrap());
+        let mut rng = Rng::from_seed(seed);
+        let mut rolls: Vec<u64> = Vec::new();
+        let mut total: i64 = 0;
+        for _ in 0..count {
+            let roll = rng.range(1, sides);
+            rolls.push(roll);
+            total += roll as i64;
+        }
+        let rolls_json: Vec<String> = rolls.iter().map(|r| r.to_string()).collect();
+        println!(
+            r#"{{"commitment":"{}","parameters":"{}","verified":true,"rolls":[{}],"total":{},"sides":{},"count":{}}}"#,
+            commitment,
+            params,
+            rolls_json.join(","),
+            total,
+            sides,
+            count
+        );
+        return;
+    }
+    // Card-deal mode: shuffle a standard 52-card deck and deal out hands.
+    if args.get(1).map(|s| s == "deal").unwrap_or(false) {
+        let hands: usize = args
+            .get(2)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1);
+        let per_hand: usize = args
+            .get(3)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(5);
+
+        if hands * per_hand > 52 {
+            eprintln!(
+                "error: cannot deal {} hands of {} from a 52-card deck",
+                hands, per_hand
+            );
+            process::exit(1);
+        }
+
+        // Build the deck as 13 ranks x 4 suits of two-character card strings.
+        const RANKS: [char; 13] = [
+            '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+        ];
+        const SUITS: [char; 4] = ['S', 'H', 'D', 'C'];
+        let mut deck: Vec<String> = Vec::with_capacity(52);
+        for &suit in &SUITS {
+            for &rank in &RANKS {
+                deck.push(format!("{}{}", rank, suit));
+            }
+        }
+
+        // Fisher-Yates shuffle driven by the crate's Rng.
+        for i in (1..deck.len()).rev() {
+            let j = if secure {
+                secure_range(0, i as u64)
+            } else {
+                rng.range(0, i as u64)
+            } as usize;
+            deck.swap(i, j);
+        }
+
+        let hands_json: Vec<String> = (0..hands)
+            .map(|h| {
+                let cards: Vec<String> = (0..per_hand)
+                    .map(|c| format!(r#""{}""#, deck[h * per_hand + c]))
+                    .collect();
+                format!("[{}]", cards.join(","))
+            })
+            .collect();
+
+        println!(
+            r#"{{"hands":[{}]{}}}"#,
+            hands_json.join(","),
+            seed_field
+        );
+        return;
+    }
+
+    let mut rolls: Vec<u64> = Vec::new();
+    let mut total: i64 = 0;
+
+    // Frequency-distribution mode: roll many times and report a histogram
+    // instead of the individual rolls. Useful as a quick RNG fairness check.
+    if let Some(rolls_n) = stats {
+        let sides: u64 = args
+            .get(1)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(6)
+            .clamp(2, 100);
+
+        // Tally counts per face in a flat Vec to stay allocation-light.
+        let mut counts: Vec<u64> = vec![0; sides as usize];
+        let mut sum: u64 = 0;
+        let mut min = u64::MAX;
+        let mut max = 0u64;
+        for _ in 0..rolls_n {
+            let roll = if secure {
+                secure_range(1, sides)
+            } else {
+                rng.range(1, sides)
+            };
+            counts[(roll - 1) as usize] += 1;
+            sum += roll;
+            min = min.min(roll);
+            max = max.max(roll);
+        }
+
+        let dist: Vec<String> = counts
+            .iter()
+            .enumerate()
+            .map(|(idx, &c)| format!(r#""{}":{}"#, idx + 1, c))
+            .collect();
+        let percents: Vec<String> = counts
+            .iter()
+            .enumerate()
+            .map(|(idx, &c)| {
+                let pct = if rolls_n > 0 {
+                    c as f64 * 100.0 / rolls_n as f64
+                } else {
+                    0.0
+                };
+                format!(r#""{}":{:.3}"#, idx + 1, pct)
+            })
+            .collect();
+        let mean = if rolls_n > 0 {
+            sum as f64 / rolls_n as f64
+        } else {
+            0.0
+        };
+        if rolls_n == 0 {
+            min = 0;
+        }
+
+        println!(
+            r#"{{"sides":{},"rolls":{},"distribution":{{{}}},"percentages":{{{}}},"min":{},"max":{},"mean":{:.3}{}}}"#,
+            sides,
+            rolls_n,
+            dist.join(","),
+            percents.join(","),
+            min,
+            max,
+            mean,
+            seed_field
+        );
+        return;
+    }
+
+    // A dice expression is recognised by the presence of `d` in the first
+    // positional argument; otherwise fall back to plain `sides`/`count` args.
+    if args.get(1).map(|s| s.contains('d')).unwrap_or(false) {
+        let expr = &args[1];
+        let parsed = match parse_expr(expr) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            }
+        };
+
+        for (count, sides) in &parsed.groups {
+            let count = (*count).clamp(1, 100);
+            let sides = (*sides).clamp(2, 100);
+            for _ in 0..count {
+                let roll = if secure {
+                    secure_range(1, sides)
+                } else {
+                    rng.range(1, sides)
+                };
+                rolls.push(roll);
+                total += roll as i64;
+            }
+        }
+        total += parsed.modifier;
+
+        let rolls_json: Vec<String> = rolls.iter().map(|r| r.to_string()).collect();
+        println!(
+            r#"{{"expression":"{}","rolls":[{}],"modifier":{},"total":{}{}}}"#,
+            expr,
+            rolls_json.join(","),
+            parsed.modifier,
+            total,
+            seed_field
+        );
+        return;
+    }
 
     // Parse arguments with defaults
     let sides: u64 = args.get(1)
@@ -44,23 +633,24 @@ fn main() {
     let count = count.clamp(1, 100);
 
     // Roll the dice
-    let mut rng = Rng::new();
-    let mut rolls: Vec<u64> = Vec::new();
-    let mut total: u64 = 0;
-
     for _ in 0..count {
-        let roll = rng.range(1, sides);
+        let roll = if secure {
+            secure_range(1, sides)
+        } else {
+            rng.range(1, sides)
+        };
         rolls.push(roll);
-        total += roll;
+        total += roll as i64;
     }
 
     // Output as JSON
     let rolls_json: Vec<String> = rolls.iter().map(|r| r.to_string()).collect();
     println!(
-        r#"{{"rolls":[{}],"total":{},"sides":{},"count":{}}}"#,
+        r#"{{"rolls":[{}],"total":{},"sides":{},"count":{}{}}}"#,
         rolls_json.join(","),
         total,
         sides,
-        count
+        count,
+        seed_field
     );
 }